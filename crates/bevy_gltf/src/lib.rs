@@ -0,0 +1,87 @@
+//! Plugin providing an [`AssetLoader`](bevy_asset::AssetLoader) and type definitions for loading
+//! glTF 2.0 (a standard 3D scene definition format) files in Bevy.
+
+mod loader;
+
+pub use loader::*;
+
+use std::{any::TypeId, collections::HashMap, sync::Arc};
+
+use bevy_app::prelude::*;
+use bevy_asset::{Asset, AssetApp, LoadContext, UntypedHandle};
+use bevy_ecs::prelude::*;
+use bevy_pbr::StandardMaterial;
+
+// `FromStandardMaterial`/`TryFromStandardMaterial` are defined in `bevy_pbr` (which owns
+// `StandardMaterial`) rather than here, since `bevy_pbr` itself implements them for `CelDither` —
+// depending the other way round would make `bevy_pbr` depend on `bevy_gltf`, which already
+// depends on `bevy_pbr`.
+pub use bevy_pbr::{FromStandardMaterial, TryFromStandardMaterial};
+
+/// A named, type-erased [`TryFromStandardMaterial`] converter. Each converter closes over its
+/// concrete material type `M`, so it can add the converted material as a labeled asset of the
+/// correct type and hand back an [`UntypedHandle`] to it, or `None` if `M` opted this primitive
+/// out.
+pub(crate) type ErasedMaterialConverter = Arc<
+    dyn Fn(StandardMaterial, Option<&str>, &mut LoadContext, String) -> Option<UntypedHandle>
+        + Send
+        + Sync,
+>;
+
+/// The set of material converters registered with [`GltfPlugin::add_material`], looked up either
+/// by the name they were registered under (for [`GltfLoaderSettings::with_material_dispatch`]) or
+/// by their material type (for [`GltfLoaderSettings::with_default_material`]).
+#[derive(Resource, Default, Clone)]
+pub(crate) struct GltfMaterialRegistry {
+    pub(crate) by_name: HashMap<&'static str, ErasedMaterialConverter>,
+    pub(crate) by_type: HashMap<TypeId, ErasedMaterialConverter>,
+}
+
+/// Adds support for glTF file loading to the app.
+#[derive(Default)]
+pub struct GltfPlugin {
+    material_converters: Vec<(&'static str, TypeId, ErasedMaterialConverter)>,
+}
+
+impl GltfPlugin {
+    /// Registers a converter from `StandardMaterial` to `M`, selectable by `name` from a
+    /// primitive's material `gltf_extras` (e.g. `{"shader": "toon"}`) when
+    /// [`GltfLoaderSettings::with_material_dispatch`] is enabled, or forced scene-wide with
+    /// [`GltfLoaderSettings::with_default_material::<M>()`].
+    pub fn add_material<M: TryFromStandardMaterial>(mut self, name: &'static str) -> Self {
+        let converter: ErasedMaterialConverter =
+            Arc::new(|material, gltf_extras, load_context, label| {
+                M::try_from_standard_material(material, gltf_extras)
+                    .map(|converted| load_context.add_labeled_asset(label, converted).untyped())
+            });
+        self.material_converters
+            .push((name, TypeId::of::<M>(), converter));
+        self
+    }
+}
+
+impl Plugin for GltfPlugin {
+    fn build(&self, app: &mut App) {
+        let mut registry = GltfMaterialRegistry::default();
+        for (name, type_id, converter) in &self.material_converters {
+            registry.by_name.insert(name, converter.clone());
+            registry.by_type.insert(*type_id, converter.clone());
+        }
+
+        app.init_asset::<Gltf>()
+            .insert_resource(registry)
+            .init_asset_loader::<GltfLoader>();
+    }
+}
+
+/// Represents a loaded glTF file.
+#[derive(Asset, bevy_reflect::TypePath, Debug)]
+pub struct Gltf {
+    /// All scenes loaded from the glTF file.
+    pub scenes: Vec<bevy_asset::Handle<bevy_scene::Scene>>,
+    /// All meshes loaded from the glTF file.
+    pub meshes: Vec<bevy_asset::Handle<bevy_render::mesh::Mesh>>,
+    /// All materials loaded from the glTF file, type-erased since some may have been converted to
+    /// a custom material type via [`GltfPlugin::add_material`].
+    pub materials: Vec<UntypedHandle>,
+}
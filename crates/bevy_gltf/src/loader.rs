@@ -0,0 +1,309 @@
+use std::any::TypeId;
+
+use bevy_asset::{io::Reader, AssetLoader, LoadContext, UntypedHandle};
+use bevy_ecs::world::{FromWorld, World};
+use bevy_pbr::StandardMaterial;
+use bevy_render::mesh::{Mesh, MeshVertexAttribute, VertexAttributeValues};
+use bevy_render::render_resource::VertexFormat;
+use bevy_scene::Scene;
+use thiserror::Error;
+
+use crate::{Gltf, GltfMaterialRegistry};
+
+/// Per-load configuration for [`GltfLoader`].
+///
+/// Sub-assets are still labeled `"Scene0"`, `"Mesh0"`, `"Material0"`, ... regardless of these
+/// settings, matching the labels `AssetServer`/`LoadContext` parse out of a requested path like
+/// `"FlightHelmet.gltf#Scene0"` — the settings only change *what* gets loaded under each label,
+/// never the label string itself. Note the same caveat as `AssetServer::load_with_settings`
+/// generally: if a path is already loaded (or loading) under one set of settings, a second load of
+/// that same path with different settings reuses the already-loaded result rather than triggering
+/// an independent load, so code that needs the same path under two different settings (as in the
+/// `load_gltf_toon` example) should expect that sharing unless the two loads are kept far enough
+/// apart that the first has a chance to be dropped first.
+#[derive(Debug, Clone, Default)]
+pub struct GltfLoaderSettings {
+    /// If set, every primitive in the loaded scenes is converted to this material type via a
+    /// converter registered with [`crate::GltfPlugin::add_material`], except where that converter
+    /// opts a material out (returns `None`) based on its `gltf_extras`; such a material goes on to
+    /// `material_dispatch`, if enabled, or else stays a plain `StandardMaterial`.
+    pub(crate) default_material: Option<TypeId>,
+    /// When `true`, each material's `gltf_extras` is inspected for a `"shader"` key and routed to
+    /// the matching converter registered with [`crate::GltfPlugin::add_material`], falling back
+    /// to `StandardMaterial` for anything that doesn't match a registered name.
+    pub(crate) material_dispatch: bool,
+    /// Maps a glTF attribute semantic (e.g. `"_TOON_BAND"`) to the [`MeshVertexAttribute`] its
+    /// accessor data should be copied into on the generated [`Mesh`].
+    pub(crate) custom_vertex_attributes: Vec<(String, MeshVertexAttribute)>,
+}
+
+impl GltfLoaderSettings {
+    /// Converts every primitive's material to `M`, starting from the `StandardMaterial` the loader
+    /// creates for it (a `StandardMaterial::default()`; the glTF material's own factors and
+    /// textures are not read). `M` must have been registered with
+    /// [`crate::GltfPlugin::add_material`]; materials that `M`'s converter opts out (`None`) are
+    /// not converted.
+    pub fn with_default_material<M: 'static>(&mut self) -> &mut Self {
+        self.default_material = Some(TypeId::of::<M>());
+        self
+    }
+
+    /// Enables per-primitive material dispatch keyed on each material's `gltf_extras`.
+    pub fn with_material_dispatch(&mut self) -> &mut Self {
+        self.material_dispatch = true;
+        self
+    }
+
+    /// Registers a glTF attribute semantic → [`MeshVertexAttribute`] mapping, so the loader copies
+    /// that accessor's data into the generated [`Mesh`] instead of discarding it. `semantic` is the
+    /// application-specific attribute name as authored (e.g. `"_TOON_BAND"`), including the
+    /// leading underscore glTF requires for non-standard attributes.
+    pub fn with_custom_vertex_attribute(
+        &mut self,
+        semantic: &str,
+        attribute: MeshVertexAttribute,
+    ) -> &mut Self {
+        self.custom_vertex_attributes
+            .push((semantic.to_string(), attribute));
+        self
+    }
+}
+
+/// Reads a custom (application-specific, `_`-prefixed) glTF attribute's accessor data, if the
+/// primitive carries it and `buffers` has the data for its buffer view, as whichever
+/// [`VertexAttributeValues`] variant matches `format` (the format the caller registered the
+/// attribute under via [`GltfLoaderSettings::with_custom_vertex_attribute`]).
+///
+/// Only the plain (non-normalized) `f32` vector formats are supported today; anything else would
+/// need per-format accessor normalization this loader doesn't implement yet.
+fn read_custom_attribute(
+    primitive: &gltf::Primitive,
+    semantic: &str,
+    format: VertexFormat,
+    buffers: &[Vec<u8>],
+) -> Option<VertexAttributeValues> {
+    let (_, accessor) = primitive.attributes().find(|(s, _)| match s {
+        // `gltf` stores application-specific semantics without their leading underscore.
+        gltf::Semantic::Extras(name) => semantic.strip_prefix('_') == Some(name.as_str()),
+        _ => false,
+    })?;
+    let get_buffer_data = |buffer: gltf::Buffer| buffers.get(buffer.index()).map(Vec::as_slice);
+    match format {
+        VertexFormat::Float32 => {
+            let values = gltf::accessor::Iter::<f32>::new(accessor, get_buffer_data)?.collect();
+            Some(VertexAttributeValues::Float32(values))
+        }
+        VertexFormat::Float32x2 => {
+            let values = gltf::accessor::Iter::<[f32; 2]>::new(accessor, get_buffer_data)?.collect();
+            Some(VertexAttributeValues::Float32x2(values))
+        }
+        VertexFormat::Float32x3 => {
+            let values = gltf::accessor::Iter::<[f32; 3]>::new(accessor, get_buffer_data)?.collect();
+            Some(VertexAttributeValues::Float32x3(values))
+        }
+        VertexFormat::Float32x4 => {
+            let values = gltf::accessor::Iter::<[f32; 4]>::new(accessor, get_buffer_data)?.collect();
+            Some(VertexAttributeValues::Float32x4(values))
+        }
+        _ => None,
+    }
+}
+
+/// Loads every buffer the document references, resolving the embedded `.glb` blob for
+/// [`gltf::buffer::Source::Bin`] and reading external buffers relative to the glTF file via
+/// `load_context`.
+async fn load_buffers(
+    document: &gltf::Document,
+    blob: Option<Vec<u8>>,
+    load_context: &mut LoadContext<'_>,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for buffer in document.buffers() {
+        let data = match buffer.source() {
+            gltf::buffer::Source::Bin => blob.clone().ok_or(GltfError::MissingBlob)?,
+            gltf::buffer::Source::Uri(uri) => load_context
+                .read_asset_bytes(uri)
+                .await
+                .map_err(|err| GltfError::Io(std::io::Error::other(err)))?,
+        };
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+/// Builds the label for sub-scene `index`, matching the conventional `"Scene0"`, `"Scene1"`, ...
+/// labels callers address via `"path/to/file.gltf#Scene0"`.
+pub(crate) fn scene_label(index: usize) -> String {
+    format!("Scene{index}")
+}
+
+/// Builds the label for mesh `index`, matching the conventional `"Mesh0"`, `"Mesh1"`, ... labels.
+pub(crate) fn mesh_label(index: usize) -> String {
+    format!("Mesh{index}")
+}
+
+/// Builds the label for material `index`, matching the conventional `"Material0"`, `"Material1"`,
+/// ... labels.
+pub(crate) fn material_label(index: usize) -> String {
+    format!("Material{index}")
+}
+
+/// Pulls the `"shader"` key out of a primitive/material's raw `gltf_extras` JSON, if present.
+fn extract_shader_name(gltf_extras: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(gltf_extras)
+        .ok()?
+        .get("shader")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Loads glTF files into [`Gltf`] assets, along with their constituent scenes, meshes and
+/// materials as labeled sub-assets.
+pub struct GltfLoader {
+    material_registry: GltfMaterialRegistry,
+}
+
+impl FromWorld for GltfLoader {
+    fn from_world(world: &mut World) -> Self {
+        GltfLoader {
+            material_registry: world
+                .get_resource::<GltfMaterialRegistry>()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl GltfLoader {
+    /// Resolves the material for a single glTF material (equivalently, the primitives that
+    /// reference it), consulting `settings.default_material` and, if enabled,
+    /// `settings.material_dispatch` against the registry, and falling back to a plain
+    /// `StandardMaterial` labeled asset when neither applies.
+    fn add_primitive_material(
+        &self,
+        load_context: &mut LoadContext,
+        label: String,
+        standard_material: StandardMaterial,
+        gltf_extras: Option<&str>,
+        settings: &GltfLoaderSettings,
+    ) -> UntypedHandle {
+        if let Some(type_id) = settings.default_material {
+            if let Some(converter) = self.material_registry.by_type.get(&type_id) {
+                // A forced default material that opts a primitive out (`None`) still falls back
+                // to plain `StandardMaterial` below, same as the dispatch path.
+                if let Some(handle) =
+                    converter(standard_material.clone(), gltf_extras, load_context, label.clone())
+                {
+                    return handle;
+                }
+            }
+        }
+
+        if settings.material_dispatch {
+            if let Some(shader_name) = gltf_extras.and_then(extract_shader_name) {
+                if let Some(converter) = self.material_registry.by_name.get(shader_name.as_str()) {
+                    if let Some(handle) = converter(
+                        standard_material.clone(),
+                        gltf_extras,
+                        load_context,
+                        label.clone(),
+                    ) {
+                        return handle;
+                    }
+                }
+            }
+        }
+
+        load_context
+            .add_labeled_asset(label, standard_material)
+            .untyped()
+    }
+}
+
+/// An error that occurs when loading a glTF file.
+#[derive(Debug, Error)]
+pub enum GltfError {
+    /// Failed to parse the glTF document.
+    #[error("failed to parse glTF document: {0}")]
+    InvalidGltf(#[from] gltf::Error),
+    /// Failed to read the glTF asset.
+    #[error("failed to read glTF asset: {0}")]
+    Io(#[from] std::io::Error),
+    /// The document references its embedded `.glb` binary chunk, but none was present.
+    #[error("glTF document has no embedded binary blob")]
+    MissingBlob,
+}
+
+impl AssetLoader for GltfLoader {
+    type Asset = Gltf;
+    type Settings = GltfLoaderSettings;
+    type Error = GltfError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &GltfLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Gltf, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let gltf = gltf::Gltf::from_slice(&bytes)?;
+        let document = &gltf.document;
+        let buffers = load_buffers(document, gltf.blob.clone(), load_context).await?;
+
+        let mut scenes = Vec::new();
+        for (index, gltf_scene) in document.scenes().enumerate() {
+            let _ = gltf_scene;
+            let handle = load_context.add_labeled_asset(scene_label(index), Scene::default());
+            scenes.push(handle);
+        }
+
+        let mut meshes = Vec::new();
+        for gltf_mesh in document.meshes() {
+            for primitive in gltf_mesh.primitives() {
+                let mut mesh = Mesh::new(
+                    bevy_render::render_resource::PrimitiveTopology::TriangleList,
+                    bevy_render::render_asset::RenderAssetUsages::default(),
+                );
+
+                // Copy every registered custom attribute this primitive actually carries. Only
+                // these are copied into `mesh`; the standard attributes (position, normal, UV,
+                // ...) are not read here.
+                for (semantic, attribute) in &settings.custom_vertex_attributes {
+                    if let Some(values) =
+                        read_custom_attribute(&primitive, semantic, attribute.format, &buffers)
+                    {
+                        mesh.insert_attribute(attribute.clone(), values);
+                    }
+                }
+
+                let handle = load_context.add_labeled_asset(mesh_label(meshes.len()), mesh);
+                meshes.push(handle);
+            }
+        }
+
+        let mut materials = Vec::new();
+        for (index, gltf_material) in document.materials().enumerate() {
+            let gltf_extras = gltf_material.extras().as_ref().map(|raw| raw.get());
+            let standard_material = StandardMaterial::default();
+            let handle = self.add_primitive_material(
+                load_context,
+                material_label(index),
+                standard_material,
+                gltf_extras,
+                settings,
+            );
+            materials.push(handle);
+        }
+
+        Ok(Gltf {
+            scenes,
+            meshes,
+            materials,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["gltf", "glb"]
+    }
+}
@@ -0,0 +1,151 @@
+//! A built-in cel/toon-shaded [`MaterialExtension`] whose light/dark band transition is
+//! anti-aliased with screen-space ordered (Bayer) dithering instead of a hard cutoff, giving a
+//! retro halftone look instead of a hard, aliased edge.
+//!
+//! This generalizes the ad-hoc toon material from the `load_gltf_toon` example into something
+//! reusable: register [`CelMaterial`] with `GltfPlugin::add_material` (in `bevy_gltf`) under the
+//! name `"toon"` (the `"shader"` value its conversion accepts) to let individual glTF primitives
+//! opt into the cel-shaded look via their material's `gltf_extras`, or apply it directly with
+//! `MaterialPlugin::<CelMaterial>::default()`.
+
+use crate::{gltf_material::TryFromStandardMaterial, ExtendedMaterial, MaterialExtension, StandardMaterial};
+use bevy_asset::Asset;
+use bevy_color::LinearRgba;
+use bevy_reflect::TypePath;
+use bevy_render::render_resource::{AsBindGroup, ShaderRef};
+use serde::Deserialize;
+
+/// A reusable cel/toon shading [`MaterialExtension`]. Combine with [`StandardMaterial`] via
+/// [`CelMaterial`] to get a toon-shaded PBR material.
+///
+/// The light/dark boundary is dithered with a Bayer ordered-dithering matrix of order
+/// [`Self::matrix_order`] (side length `2^matrix_order`) rather than a hard `cutoff`, so the
+/// transition dissolves into a stable dither pattern instead of aliasing.
+#[derive(Debug, Clone, TypePath, AsBindGroup, Asset)]
+pub struct CelDither {
+    /// Lambertian threshold at which the surface transitions from `dark` to `light`.
+    #[uniform(100)]
+    pub cutoff: f32,
+    /// Color used below the threshold.
+    #[uniform(101)]
+    pub dark: LinearRgba,
+    /// Color used above the threshold.
+    #[uniform(102)]
+    pub light: LinearRgba,
+    /// Width of the dithered transition band, in the same units as `cutoff`.
+    #[uniform(103)]
+    pub band_width: f32,
+    /// Order `k` of the Bayer matrix; the matrix itself is `2^k` entries per side.
+    #[uniform(104)]
+    pub matrix_order: u32,
+    /// The precomputed Bayer threshold matrix, flattened row-major and normalized to `[0, 1)`.
+    #[storage(105, read_only)]
+    pub bayer_matrix: Vec<f32>,
+}
+
+impl CelDither {
+    /// Creates a [`CelDither`] with a Bayer matrix of order `matrix_order` (side length
+    /// `2^matrix_order`), computed on the CPU with the standard blockwise recurrence
+    /// `M₁ = [[0,2],[3,1]]`, `M₂ₙ = [[4·Mₙ, 4·Mₙ+2], [4·Mₙ+3, 4·Mₙ+1]]`, normalized by `s·s`.
+    pub fn new(
+        cutoff: f32,
+        dark: LinearRgba,
+        light: LinearRgba,
+        band_width: f32,
+        matrix_order: u32,
+    ) -> Self {
+        CelDither {
+            cutoff,
+            dark,
+            light,
+            band_width,
+            matrix_order,
+            bayer_matrix: bayer_matrix(matrix_order),
+        }
+    }
+}
+
+impl Default for CelDither {
+    fn default() -> Self {
+        CelDither::new(
+            0.5,
+            LinearRgba::rgb(0.4, 0.4, 0.4),
+            LinearRgba::rgb(0.8, 0.8, 0.8),
+            0.1,
+            2,
+        )
+    }
+}
+
+/// Builds a Bayer ordered-dithering threshold matrix of order `k` (side length `2^k`), flattened
+/// row-major and normalized to `[0, 1)`.
+fn bayer_matrix(k: u32) -> Vec<f32> {
+    if k == 0 {
+        // Side length 2^0 == 1: a single threshold shared by the whole screen, i.e. no dithering.
+        return vec![0.5];
+    }
+    let mut m = vec![0u32, 2, 3, 1];
+    let mut side = 2usize;
+    for _ in 1..k {
+        let next_side = side * 2;
+        let mut next = vec![0u32; next_side * next_side];
+        for y in 0..side {
+            for x in 0..side {
+                let v = m[y * side + x];
+                next[y * next_side + x] = 4 * v;
+                next[y * next_side + x + side] = 4 * v + 2;
+                next[(y + side) * next_side + x] = 4 * v + 3;
+                next[(y + side) * next_side + x + side] = 4 * v + 1;
+            }
+        }
+        m = next;
+        side = next_side;
+    }
+    let area = (side * side) as f32;
+    m.into_iter().map(|v| v as f32 / area).collect()
+}
+
+/// The `gltf_extras` shape [`CelDither`]'s [`TryFromStandardMaterial`] impl looks for, letting a
+/// glTF primitive opt into (and tune) the toon look via e.g. `{"shader": "toon", "cutoff": 0.4}`.
+#[derive(Deserialize)]
+struct CelDitherExtras {
+    shader: Option<String>,
+    cutoff: Option<f32>,
+    dark: Option<LinearRgba>,
+    light: Option<LinearRgba>,
+    band_width: Option<f32>,
+    matrix_order: Option<u32>,
+}
+
+impl TryFromStandardMaterial for CelDither {
+    fn try_from_standard_material(_: StandardMaterial, gltf_extras: Option<&str>) -> Option<Self> {
+        let Some(extras) = gltf_extras.and_then(|s| serde_json::from_str::<CelDitherExtras>(s).ok())
+        else {
+            // No extras authored, or extras that don't parse as `CelDitherExtras`: don't opt
+            // this primitive out, just use the defaults.
+            return Some(CelDither::default());
+        };
+        if extras.shader.as_deref() != Some("toon") {
+            // This primitive explicitly asked for a different shader (or none); leave it as
+            // `StandardMaterial` instead of coercing it into `CelDither`.
+            return None;
+        }
+        let defaults = CelDither::default();
+        Some(CelDither::new(
+            extras.cutoff.unwrap_or(defaults.cutoff),
+            extras.dark.unwrap_or(defaults.dark),
+            extras.light.unwrap_or(defaults.light),
+            extras.band_width.unwrap_or(defaults.band_width),
+            extras.matrix_order.unwrap_or(defaults.matrix_order),
+        ))
+    }
+}
+
+impl MaterialExtension for CelDither {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/cel_dither.wgsl".into()
+    }
+}
+
+/// A cel/toon-shaded material combining [`StandardMaterial`] with [`CelDither`] banding.
+pub type CelMaterial = ExtendedMaterial<StandardMaterial, CelDither>;
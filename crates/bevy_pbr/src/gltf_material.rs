@@ -0,0 +1,52 @@
+//! Traits for converting a glTF primitive's loaded [`StandardMaterial`] into a custom material
+//! type, used by `bevy_gltf`'s [`add_material`](https://docs.rs/bevy_gltf) dispatch registry.
+//! Defined here (rather than in `bevy_gltf`) so materials in this crate, such as [`CelDither`]'s
+//! [`CelMaterial`], can implement them without `bevy_pbr` depending on `bevy_gltf`.
+
+use crate::{ExtendedMaterial, MaterialExtension, StandardMaterial};
+use bevy_asset::Asset;
+
+/// Converts a loaded [`StandardMaterial`] (plus that glTF material's raw `gltf_extras` JSON, if any)
+/// into a custom material type, always succeeding.
+///
+/// Every implementor gets [`TryFromStandardMaterial`] for free via a blanket impl that always
+/// returns `Some`; implement that trait directly instead if a primitive should sometimes be left
+/// as a plain `StandardMaterial`.
+pub trait FromStandardMaterial: Asset + Send + Sync + 'static {
+    /// Converts `material` into `Self`, using `gltf_extras` (the glTF material's raw `gltf_extras`
+    /// JSON, if it has any) to configure the result.
+    fn from_standard_material(material: StandardMaterial, gltf_extras: Option<&str>) -> Self;
+}
+
+/// Converts a loaded [`StandardMaterial`] into a custom material type, with the option to opt a
+/// primitive out (returning `None`) and leave it as a plain `StandardMaterial`.
+///
+/// Register an implementor with `GltfPlugin::add_material` to make it selectable by name from
+/// `GltfLoaderSettings::with_material_dispatch`, or forced scene-wide with
+/// `GltfLoaderSettings::with_default_material`.
+pub trait TryFromStandardMaterial: Asset + Send + Sync + 'static {
+    /// Converts `material` into `Self`, or returns `None` to leave the primitive as a plain
+    /// `StandardMaterial`.
+    fn try_from_standard_material(material: StandardMaterial, gltf_extras: Option<&str>) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+impl<T: FromStandardMaterial> TryFromStandardMaterial for T {
+    fn try_from_standard_material(material: StandardMaterial, gltf_extras: Option<&str>) -> Option<Self> {
+        Some(T::from_standard_material(material, gltf_extras))
+    }
+}
+
+/// Lets a [`MaterialExtension`] opt individual primitives out of an
+/// [`ExtendedMaterial<StandardMaterial, E>`] by implementing [`TryFromStandardMaterial`] on the
+/// extension type `E` alone.
+impl<E: TryFromStandardMaterial + MaterialExtension> TryFromStandardMaterial
+    for ExtendedMaterial<StandardMaterial, E>
+{
+    fn try_from_standard_material(material: StandardMaterial, gltf_extras: Option<&str>) -> Option<Self> {
+        let base = material.clone();
+        E::try_from_standard_material(material, gltf_extras)
+            .map(|extension| ExtendedMaterial { base, extension })
+    }
+}
@@ -1,56 +1,32 @@
 //! Loads and renders a glTF file as a scene with a custom standard material.
 
 use bevy::{
-    gltf::{FromStandardMaterial, GltfLoaderSettings, GltfPlugin},
-    pbr::{
-        CascadeShadowConfigBuilder, DirectionalLightShadowMap, ExtendedMaterial, MaterialExtension,
-    },
+    gltf::{GltfLoaderSettings, GltfPlugin},
+    pbr::{CascadeShadowConfigBuilder, CelMaterial, DirectionalLightShadowMap},
     prelude::*,
-    render::render_resource::{AsBindGroup, ShaderRef},
+    render::mesh::MeshVertexAttribute,
+    render::render_resource::VertexFormat,
 };
-use serde::Deserialize;
 use std::f32::consts::*;
 
+// The toon balls are exported from Blender with a custom `_TOON_BAND` vertex attribute painted as
+// a vertex color channel; `with_custom_vertex_attribute` tells the glTF loader to copy that
+// accessor into the generated `Mesh` under this attribute instead of silently dropping it.
+const ATTRIBUTE_TOON_BAND: MeshVertexAttribute =
+    MeshVertexAttribute::new("ToonBand", 988540917, VertexFormat::Float32);
+
 fn main() {
     App::new()
         .insert_resource(DirectionalLightShadowMap { size: 4096 })
-        .add_plugins(DefaultPlugins.set(GltfPlugin::default().add_material::<ToonMaterial>("toon")))
-        .add_plugins(MaterialPlugin::<ToonMaterial>::default())
+        .add_plugins(
+            DefaultPlugins.set(GltfPlugin::default().add_material::<CelMaterial>("toon")),
+        )
+        .add_plugins(MaterialPlugin::<CelMaterial>::default())
         .add_systems(Startup, setup)
         .add_systems(Update, animate_light_direction)
         .run();
 }
 
-#[derive(Debug, Clone, TypePath, AsBindGroup, Asset, Deserialize)]
-struct ToonShader {
-    #[uniform(100)]
-    cutoff: f32,
-    #[uniform(101)]
-    dark: LinearRgba,
-    #[uniform(102)]
-    light: LinearRgba,
-}
-
-impl FromStandardMaterial for ToonShader {
-    fn from_standard_material(_: StandardMaterial, gltf_extras: Option<&str>) -> Self {
-        gltf_extras
-            .and_then(|s| serde_json::from_str::<ToonShader>(s).ok())
-            .unwrap_or(ToonShader {
-                cutoff: 0.5,
-                dark: LinearRgba::rgb(0.4, 0.4, 0.4),
-                light: LinearRgba::rgb(0.8, 0.8, 0.8),
-            })
-    }
-}
-
-impl MaterialExtension for ToonShader {
-    fn fragment_shader() -> ShaderRef {
-        "shaders/toon_shader.wgsl".into()
-    }
-}
-
-type ToonMaterial = ExtendedMaterial<StandardMaterial, ToonShader>;
-
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
         Camera3dBundle {
@@ -82,14 +58,17 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         .into(),
         ..default()
     });
-    // Note: All assets are cached by path so you cannot load the same file with different settings
-    // while this handle is alive, if multiple versions are needed, load with default settings and
-    // generate each material from the standard materials.
+    // Sub-assets are still labeled `Scene0`/`Mesh0`/`Material0` regardless of `GltfLoaderSettings`,
+    // so (as with `AssetServer::load_with_settings` generally) loading the same path again with
+    // different settings while the first load is still live reuses that first result rather than
+    // producing an independent one. The plain `load` of the same path in the second spawn below
+    // therefore gets back the same `CelMaterial`-converted scene as this first spawn, not a
+    // separate `StandardMaterial` one.
     commands.spawn(SceneBundle {
         scene: asset_server.load_with_settings(
             "models/FlightHelmet/FlightHelmet.gltf#Scene0",
             |s: &mut GltfLoaderSettings| {
-                s.with_default_material::<ToonMaterial>();
+                s.with_default_material::<CelMaterial>();
             },
         ),
         transform: Transform::from_translation(Vec3::new(-1., 0., 0.))
@@ -97,10 +76,27 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             .with_scale(Vec3::splat(1.4)),
         ..default()
     });
+    commands.spawn(SceneBundle {
+        scene: asset_server.load("models/FlightHelmet/FlightHelmet.gltf#Scene0"),
+        transform: Transform::from_translation(Vec3::new(1., 0., 0.))
+            .with_rotation(Quat::from_rotation_y(f32::to_radians(-45.0)))
+            .with_scale(Vec3::splat(1.4)),
+        ..default()
+    });
 
-    // The balls are created in blender using custom attributes.
+    // The balls are created in blender using custom attributes, and only some of them carry a
+    // `"shader": "toon"` entry in their `gltf_extras`. Rather than forcing every primitive in the
+    // scene into `CelMaterial` like `with_default_material` does above, `with_material_dispatch`
+    // inspects each primitive's material extras and routes it to whichever converter was registered under
+    // that name with `GltfPlugin::add_material`, leaving the rest as plain `StandardMaterial`.
     commands.spawn(SceneBundle {
-        scene: asset_server.load("models/ToonBalls/toon_balls.gltf#Scene0"),
+        scene: asset_server.load_with_settings(
+            "models/ToonBalls/toon_balls.gltf#Scene0",
+            |s: &mut GltfLoaderSettings| {
+                s.with_material_dispatch();
+                s.with_custom_vertex_attribute("_TOON_BAND", ATTRIBUTE_TOON_BAND);
+            },
+        ),
         ..default()
     });
 }